@@ -1,5 +1,8 @@
+use std::ffi::OsStr;
 use std::iter;
 
+use os_str_bytes::OsStrBytes;
+
 use crate::util::eq_ignore_case;
 
 /// The representation of a possible value of an argument.
@@ -103,6 +106,87 @@ impl<'help> PossibleValue<'help> {
             self.get_name_and_aliases().any(|name| name == value)
         }
     }
+
+    /// Tests if the value is valid for this argument value
+    ///
+    /// This is the [`OsStr`] counterpart to [`matches`][PossibleValue::matches()]. The name and
+    /// aliases are compared against the platform's raw byte representation of `value` rather
+    /// than going through a (potentially lossy) `&str` conversion first, so values that aren't
+    /// valid UTF-8 are either matched byte-for-byte or cleanly rejected instead of silently
+    /// mangled into an unrelated match.
+    ///
+    /// `ignore_case` folds ASCII case on the raw bytes, so it still applies to values that
+    /// aren't valid UTF-8 (e.g. paths with a non-UTF-8 component next to ASCII casing); it just
+    /// can't fold non-ASCII bytes, since doing so would require assuming an encoding for bytes
+    /// that have none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::ffi::OsStr;
+    /// # use clap::PossibleValue;
+    /// let arg_value = PossibleValue::new("fast").alias("not-slow");
+    ///
+    /// assert!(arg_value.matches_os(OsStr::new("fast"), false));
+    /// assert!(arg_value.matches_os(OsStr::new("not-slow"), false));
+    ///
+    /// assert!(arg_value.matches_os(OsStr::new("FAST"), true));
+    /// assert!(!arg_value.matches_os(OsStr::new("FAST"), false));
+    /// ```
+    pub fn matches_os(&self, value: &OsStr, ignore_case: bool) -> bool {
+        let value = value.to_raw_bytes();
+        self.get_name_and_aliases().any(|name| {
+            let name = OsStr::new(name).to_raw_bytes();
+            if ignore_case {
+                name.eq_ignore_ascii_case(&value)
+            } else {
+                name == value
+            }
+        })
+    }
+}
+
+/// Suggests the closest possible value to `value` out of `possible_values`, for use in "did you
+/// mean" style error messages.
+///
+/// [`PossibleValue`]s that are [hidden] are never suggested, since they aren't valid input for
+/// the user to discover and retype. The candidates are the same name and aliases that [`matches`]
+/// compares against; each is scored by Jaro-Winkler similarity to `value`, and only a candidate
+/// scoring above `0.7` is returned.
+///
+/// **NOTE:** Nothing in this tree calls this function yet. The validation path that produces the
+/// "invalid value" error (and that this is meant to feed a `did you mean '{}'?` suggestion into)
+/// is not part of this snapshot, so the suggestion can't be threaded into a real error here. This
+/// is left `pub(crate)` and ready to be called from that path once it exists.
+///
+/// [hidden]: PossibleValue::is_hidden()
+/// [`matches`]: PossibleValue::matches()
+// Only exercised by `mod tests` below until the validation path above exists to call it for
+// real; without this, a plain `cargo build`/`cargo clippy` (no `--tests`) flags it as dead code.
+#[allow(dead_code)]
+pub(crate) fn suggest_closest_possible_value<'a, 'help>(
+    value: &str,
+    possible_values: impl IntoIterator<Item = &'a PossibleValue<'help>>,
+) -> Option<&'a str>
+where
+    'help: 'a,
+{
+    let mut best: Option<(&str, f64)> = None;
+
+    for pv in possible_values {
+        if pv.is_hidden() {
+            continue;
+        }
+
+        for name in pv.get_name_and_aliases() {
+            let score = strsim::jaro_winkler(value, name);
+            if score > 0.7 && best.map_or(true, |(_, best_score)| score > best_score) {
+                best = Some((name, score));
+            }
+        }
+    }
+
+    best.map(|(name, _)| name)
 }
 
 impl<'help> PossibleValue<'help> {
@@ -203,3 +287,87 @@ impl<'help> PossibleValue<'help> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference vectors taken from the Jaro-Winkler papers/Wikipedia, to pin `strsim`'s
+    // behavior to the scores `suggest_closest_possible_value`'s `0.7` threshold relies on.
+    #[test]
+    fn jaro_winkler_reference_vectors() {
+        assert!((strsim::jaro_winkler("MARTHA", "MARHTA") - 0.961).abs() < 0.001);
+        assert!((strsim::jaro_winkler("DIXON", "DICKSONX") - 0.813).abs() < 0.001);
+        assert!((strsim::jaro_winkler("fast", "fast") - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn suggest_closest_possible_value_picks_nearest_match() {
+        let fast = PossibleValue::new("fast");
+        let slow = PossibleValue::new("slow");
+        let values = [fast, slow];
+
+        assert_eq!(
+            suggest_closest_possible_value("fsat", &values),
+            Some("fast")
+        );
+        assert_eq!(
+            suggest_closest_possible_value("sloww", &values),
+            Some("slow")
+        );
+        assert_eq!(suggest_closest_possible_value("xyz", &values), None);
+    }
+
+    #[test]
+    fn suggest_closest_possible_value_ignores_hidden_values() {
+        let values = [
+            PossibleValue::new("fast"),
+            PossibleValue::new("secret").hidden(true),
+        ];
+
+        assert_eq!(suggest_closest_possible_value("secrt", &values), None);
+    }
+
+    #[test]
+    fn suggest_closest_possible_value_matches_against_aliases() {
+        let values = [PossibleValue::new("fast").alias("quick")];
+
+        assert_eq!(
+            suggest_closest_possible_value("quik", &values),
+            Some("quick")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn matches_os_rejects_invalid_utf8_cleanly() {
+        use std::os::unix::ffi::OsStrExt;
+
+        // `\xFF` on its own is not valid UTF-8, and `PossibleValue` names are always valid `str`,
+        // so this can never be equal to any candidate's raw bytes -- it must be rejected outright
+        // rather than panicking or being silently mangled by a lossy conversion.
+        let invalid_utf8 = OsStr::from_bytes(b"fa\xFFst");
+        let arg_value = PossibleValue::new("fast").alias("quick");
+
+        assert!(!arg_value.matches_os(invalid_utf8, false));
+        assert!(!arg_value.matches_os(invalid_utf8, true));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn matches_os_ignore_case_folds_ascii_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let arg_value = PossibleValue::new("fast");
+
+        // Goes through the same raw-byte path as the invalid-UTF-8 case above (rather than a
+        // `&str` conversion), so this doubles as a regression test that `ignore_case` isn't
+        // silently dropped for values built via `from_bytes`.
+        let same_case = OsStr::from_bytes(b"fast");
+        let different_case = OsStr::from_bytes(b"FAST");
+
+        assert!(arg_value.matches_os(same_case, false));
+        assert!(!arg_value.matches_os(different_case, false));
+        assert!(arg_value.matches_os(different_case, true));
+    }
+}